@@ -9,12 +9,30 @@
 //! - **Compliance**: GDPR, HIPAA, SOC 2, PCI DSS support
 //! - **Resource Management**: Per-tenant quotas and limits
 //! - **Audit Logging**: Configurable audit settings
+//! - **Validation**: `validate()` derived from protoc-gen-validate constraints
+//! - **Reflection**: embedded `FILE_DESCRIPTOR_SET` for server reflection and dynamic decoding
+//!
+//! ## Cargo Features
+//!
+//! - `server` / `client`: control whether gRPC server and/or client stubs are generated
+//! - `serde`: derive `Serialize`/`Deserialize` (camelCase) on every generated message
+
+pub use api_contracts_build::{Validate, ValidationError};
+
+/// Compiled `FileDescriptorSet` for `core.v1`, for registering with
+/// `tonic-reflection` (see [`api_contracts_build::reflection`]) or
+/// decoding messages dynamically.
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/file_descriptor_set.bin"));
 
 // Include generated protobuf code from OUT_DIR
 // The build.rs script generates these files during compilation
 pub mod core {
     pub mod v1 {
+        use api_contracts_build::{Validate, ValidationError};
+
         include!(concat!(env!("OUT_DIR"), "/core.v1.rs"));
+        include!(concat!(env!("OUT_DIR"), "/validate.rs"));
     }
 }
 
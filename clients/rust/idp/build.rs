@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use api_contracts_build::{GOOGLEAPIS, PROTOC_GEN_VALIDATE};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Get the absolute path to the project root
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")?;
+    let project_root = PathBuf::from(&manifest_dir)
+        .join("../../..")
+        .canonicalize()?;
+
+    let idp_proto_dir = project_root.join("proto").join("idp");
+
+    api_contracts_build::Builder::new(project_root)
+        .discover(idp_proto_dir)?
+        .with_git_fallback_deps(&[GOOGLEAPIS, PROTOC_GEN_VALIDATE])
+        .with_buf_export()
+        .with_feature_gated_codegen(
+            cfg!(feature = "server"),
+            cfg!(feature = "client"),
+            cfg!(feature = "serde"),
+        )
+        .with_validation()
+        .compile()
+}
@@ -2,40 +2,39 @@
 //!
 //! Identity and access management API contracts for authentication,
 //! user management, organizations, and RBAC.
+//!
+//! ## Cargo Features
+//!
+//! - `server` / `client`: control whether gRPC server and/or client stubs are generated
+//! - `serde`: derive `Serialize`/`Deserialize` (camelCase) on every generated message
 
-pub mod idp {
-    pub mod v1 {
-        pub mod auth {
-            // Authentication services
-        }
-
-        pub mod user {
-            // User management
-        }
-
-        pub mod organization {
-            // Organization management
-        }
+pub use api_contracts_build::{Validate, ValidationError};
 
-        pub mod role {
-            // Role management
-        }
+/// Compiled `FileDescriptorSet` for `idp.v1`, for registering with
+/// `tonic-reflection` (see [`api_contracts_build::reflection`]) or
+/// decoding messages dynamically.
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/file_descriptor_set.bin"));
 
-        pub mod permission {
-            // Permission management
-        }
+// Include generated protobuf code from OUT_DIR.
+// The build.rs script generates these files during compilation.
+pub mod idp {
+    pub mod v1 {
+        use api_contracts_build::{Validate, ValidationError};
 
-        pub mod session {
-            // Session management
-        }
+        include!(concat!(env!("OUT_DIR"), "/idp.v1.rs"));
+        include!(concat!(env!("OUT_DIR"), "/validate.rs"));
     }
 }
 
+// Re-export for convenience
+pub use idp::v1;
+
 #[cfg(test)]
 mod tests {
     #[test]
     fn test_module_structure() {
-        // Verify module structure compiles correctly
-        // Actual tests will be added once proto code is generated
+        // Verify module structure compiles correctly.
+        // Actual tests will be added once proto code is generated.
     }
 }
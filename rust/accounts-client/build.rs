@@ -1,13 +1,25 @@
+use std::path::PathBuf;
+
+use api_contracts_build::{GOOGLEAPIS, PROTOC_GEN_VALIDATE};
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tonic_build::configure()
-        .build_server(false)
-        .build_client(true)
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")?;
+    let project_root = PathBuf::from(&manifest_dir)
+        .join("../..")
+        .canonicalize()?;
+
+    let accounts_proto_dir = project_root.join("proto").join("accounts");
+
+    api_contracts_build::Builder::new(project_root)
+        .discover(accounts_proto_dir)?
+        .with_git_fallback_deps(&[GOOGLEAPIS, PROTOC_GEN_VALIDATE])
+        .with_buf_export()
+        .with_feature_gated_codegen(
+            cfg!(feature = "server"),
+            cfg!(feature = "client"),
+            cfg!(feature = "serde"),
+        )
         .out_dir("src/proto")
-        .compile(
-            &[
-                "../../proto/accounts/v1/accounts.proto",
-            ],
-            &["../../proto"],
-        )?;
-    Ok(())
-}
\ No newline at end of file
+        .with_validation()
+        .compile()
+}
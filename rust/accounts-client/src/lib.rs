@@ -1,6 +1,17 @@
+pub use api_contracts_build::{Validate, ValidationError};
+
+/// Compiled `FileDescriptorSet` for `accounts.v1`, for registering with
+/// `tonic-reflection` (see [`api_contracts_build::reflection`]) or
+/// decoding messages dynamically.
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/file_descriptor_set.bin"));
+
 pub mod accounts {
     pub mod v1 {
+        use api_contracts_build::{Validate, ValidationError};
+
         tonic::include_proto!("accounts.v1");
+        include!(concat!(env!("OUT_DIR"), "/validate.rs"));
     }
 }
 
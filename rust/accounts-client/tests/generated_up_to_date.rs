@@ -0,0 +1,31 @@
+//! Fails if `src/proto` drifts from what the accounts `.proto` files
+//! would regenerate. See `api_contracts_build::assert_generated_up_to_date`.
+//!
+//! The `Builder` chain below must mirror `build.rs` exactly, or this test
+//! compares against the wrong configuration instead of catching real drift.
+
+use std::path::PathBuf;
+
+use api_contracts_build::{Builder, GOOGLEAPIS, PROTOC_GEN_VALIDATE};
+
+#[test]
+fn generated_code_is_up_to_date() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let project_root = manifest_dir.join("../..").canonicalize().unwrap();
+    let accounts_proto_dir = project_root.join("proto").join("accounts");
+
+    let builder = Builder::new(project_root)
+        .discover(accounts_proto_dir)
+        .unwrap()
+        .with_git_fallback_deps(&[GOOGLEAPIS, PROTOC_GEN_VALIDATE])
+        .with_buf_export()
+        .with_feature_gated_codegen(
+            cfg!(feature = "server"),
+            cfg!(feature = "client"),
+            cfg!(feature = "serde"),
+        )
+        .with_validation();
+
+    api_contracts_build::assert_generated_up_to_date(builder, &manifest_dir.join("src/proto"))
+        .unwrap();
+}
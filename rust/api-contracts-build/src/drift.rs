@@ -0,0 +1,102 @@
+//! Drift detection for checked-in generated code.
+//!
+//! Crates like `accounts-client` and `auth-client` commit generated code
+//! into `src/proto` instead of reading it from `OUT_DIR`, so it rots
+//! whenever a `.proto` changes without a rebuild. [`assert_generated_up_to_date`]
+//! takes the exact same [`crate::Builder`] chain the crate's `build.rs`
+//! uses, reruns it into a scratch directory, and byte-compares the result
+//! against what's checked in. Reusing the real builder (rather than a
+//! hand-rolled `tonic_build::configure()`) keeps this in sync with
+//! whatever include dirs, feature gating, and validation the crate
+//! actually builds with. In practice `cargo build` already refreshes
+//! `src/proto` as a side effect (its `out_dir` points there), so this test
+//! only catches the case where someone edited a `.proto` and forgot to
+//! rebuild before committing.
+
+use std::path::{Path, PathBuf};
+
+use crate::{Builder, BuildResult};
+
+/// Rerun `builder` into a scratch directory and assert the result matches
+/// `checked_in_dir` byte-for-byte. `builder` should be configured exactly
+/// like the crate's `build.rs` (same `discover`, include dirs, feature
+/// gating, validation), so the only variable is the `.proto` sources.
+pub fn assert_generated_up_to_date(builder: Builder, checked_in_dir: &Path) -> BuildResult<()> {
+    let scratch_dir =
+        std::env::temp_dir().join(format!("api-contracts-build-drift-{}", std::process::id()));
+    std::fs::create_dir_all(&scratch_dir)?;
+
+    let previous_out_dir = std::env::var_os("OUT_DIR");
+    // `compile` reads/writes intermediates (the descriptor set, validate.rs)
+    // relative to OUT_DIR, not just the explicit `out_dir`, so redirect both.
+    std::env::set_var("OUT_DIR", &scratch_dir);
+
+    let result = builder
+        .out_dir(&scratch_dir)
+        .compile()
+        .and_then(|()| diff_dirs(checked_in_dir, &scratch_dir));
+
+    match previous_out_dir {
+        Some(dir) => std::env::set_var("OUT_DIR", dir),
+        None => std::env::remove_var("OUT_DIR"),
+    }
+    let _ = std::fs::remove_dir_all(&scratch_dir);
+
+    match result? {
+        mismatches if mismatches.is_empty() => Ok(()),
+        mismatches => Err(format!(
+            "checked-in generated code in {} is stale; rerun `cargo build` to refresh it:\n{}",
+            checked_in_dir.display(),
+            mismatches.join("\n")
+        )
+        .into()),
+    }
+}
+
+/// Compare the `.rs` files in two directories, returning a human-readable
+/// mismatch description per differing file (empty if they match).
+fn diff_dirs(checked_in_dir: &Path, regenerated_dir: &Path) -> BuildResult<Vec<String>> {
+    let checked_in_files = rust_files_in(checked_in_dir)?;
+    let regenerated_files = rust_files_in(regenerated_dir)?;
+
+    let checked_in_names: Vec<_> = checked_in_files.iter().filter_map(|p| p.file_name()).collect();
+    let regenerated_names: Vec<_> = regenerated_files
+        .iter()
+        .filter_map(|p| p.file_name())
+        .collect();
+
+    if checked_in_names != regenerated_names {
+        return Ok(vec![format!(
+            "file set differs: checked-in {checked_in_names:?} vs regenerated {regenerated_names:?}"
+        )]);
+    }
+
+    let mut mismatches = Vec::new();
+    for (checked_in_file, regenerated_file) in checked_in_files.iter().zip(&regenerated_files) {
+        let checked_in_contents = std::fs::read_to_string(checked_in_file)?;
+        let regenerated_contents = std::fs::read_to_string(regenerated_file)?;
+        if checked_in_contents != regenerated_contents {
+            mismatches.push(format!(
+                "{} does not match freshly generated output",
+                checked_in_file.display()
+            ));
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// List `.rs` files directly inside `dir`, sorted by name. `validate.rs`
+/// is generated alongside the tonic-build output but is never checked
+/// into `src/proto` (it's `include!`d straight from `OUT_DIR`), so it's
+/// excluded from the drift comparison.
+fn rust_files_in(dir: &Path) -> BuildResult<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("rs"))
+        .filter(|path| path.file_name().and_then(|n| n.to_str()) != Some("validate.rs"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
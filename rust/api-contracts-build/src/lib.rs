@@ -0,0 +1,289 @@
+//! Shared `build.rs` support for the api-contracts subcrates.
+//!
+//! Every subcrate under `rust/` and `clients/rust/` needs to discover its
+//! `.proto` files, emit `cargo:rerun-if-changed` for them, optionally
+//! resolve `buf export`'d dependencies, and invoke `tonic_build` with a
+//! consistent configuration. This crate centralizes that pipeline behind
+//! a small builder so each `build.rs` stays a thin, declarative call site
+//! instead of re-implementing discovery, `protoc` resolution, and buf
+//! plumbing.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+mod drift;
+pub mod reflection;
+mod toolchain;
+mod validate;
+mod vendor;
+
+pub use drift::assert_generated_up_to_date;
+pub use toolchain::resolve_protoc;
+pub use validate::{Validate, ValidationError};
+pub use vendor::{GitDependency, GOOGLEAPIS, PROTOC_GEN_VALIDATE};
+
+/// Convenience alias matching the `Box<dyn Error>` return type `build.rs`
+/// entry points use.
+pub type BuildResult<T> = Result<T, Box<dyn Error>>;
+
+/// Which of a crate's `server`/`client`/`serde` Cargo features are active,
+/// passed in explicitly (via `cfg!(feature = "...")`) by whoever builds the
+/// [`Builder`] chain, so the same chain produces the same codegen whether
+/// it runs from `build.rs` or from a test binary. See
+/// [`Builder::with_feature_gated_codegen`].
+#[derive(Debug, Clone, Copy)]
+struct FeatureGates {
+    server: bool,
+    client: bool,
+    serde: bool,
+}
+
+/// Builds the discovery + compile pipeline shared by every subcrate's
+/// `build.rs`.
+pub struct Builder {
+    package_root: PathBuf,
+    proto_files: Vec<PathBuf>,
+    include_dirs: Vec<PathBuf>,
+    buf_export: bool,
+    feature_gates: Option<FeatureGates>,
+    out_dir: Option<PathBuf>,
+    validate: bool,
+    git_fallback_deps: Vec<GitDependency>,
+}
+
+impl Builder {
+    /// Start a new pipeline rooted at `package_root`, the workspace root
+    /// containing `proto/`, `buf.yaml`, and `buf.lock`.
+    pub fn new(package_root: impl Into<PathBuf>) -> Self {
+        Self {
+            package_root: package_root.into(),
+            proto_files: Vec::new(),
+            include_dirs: Vec::new(),
+            buf_export: false,
+            feature_gates: None,
+            out_dir: None,
+            validate: false,
+            git_fallback_deps: Vec::new(),
+        }
+    }
+
+    /// Recursively discover all `.proto` files under `dir`, emitting
+    /// `cargo:rerun-if-changed` for `dir`, every discovered file, and
+    /// `buf.yaml`/`buf.lock`. The containing `proto/` directory is added
+    /// to the include path automatically.
+    pub fn discover(mut self, dir: impl AsRef<Path>) -> BuildResult<Self> {
+        let dir = dir.as_ref();
+
+        println!("cargo:rerun-if-changed={}", dir.display());
+        println!(
+            "cargo:rerun-if-changed={}",
+            self.package_root.join("buf.yaml").display()
+        );
+        println!(
+            "cargo:rerun-if-changed={}",
+            self.package_root.join("buf.lock").display()
+        );
+
+        let mut discovered = discover_proto_files(dir)?;
+        for proto_file in &discovered {
+            println!("cargo:rerun-if-changed={}", proto_file.display());
+        }
+        self.proto_files.append(&mut discovered);
+
+        let proto_root = self.package_root.join("proto");
+        if !self.include_dirs.contains(&proto_root) {
+            self.include_dirs.push(proto_root);
+        }
+
+        Ok(self)
+    }
+
+    /// Export buf dependencies (e.g. googleapis) into `OUT_DIR/buf_deps`
+    /// and add the result to the include path during `compile`. Falls
+    /// back to a `cargo:warning` if `buf` isn't installed.
+    pub fn with_buf_export(mut self) -> Self {
+        self.buf_export = true;
+        self
+    }
+
+    /// Toggle `build_server`/`build_client` based on `server`/`client`,
+    /// and derive `serde::Serialize`/`Deserialize` on every generated
+    /// message when `serde` is set. Pass `cfg!(feature = "server")` (and
+    /// so on) at the call site rather than hardcoding one mode — callers
+    /// in `build.rs` and in a drift test like
+    /// `tests/generated_up_to_date.rs` must pass the *same* `cfg!()`
+    /// expressions so both compile the identical configuration. This used
+    /// to read `CARGO_FEATURE_*` env vars internally instead, which Cargo
+    /// only sets for the actual build script process — a test binary
+    /// calling this method directly would always see them unset and
+    /// silently diverge from what `build.rs` really produced.
+    ///
+    /// Neither `server` nor `client` set (e.g. `cargo check
+    /// --no-default-features`) falls back to client-only, matching the
+    /// subcrates' `default = ["client"]`.
+    pub fn with_feature_gated_codegen(mut self, server: bool, client: bool, serde: bool) -> Self {
+        self.feature_gates = Some(FeatureGates { server, client, serde });
+        self
+    }
+
+    /// Write generated code to `dir` instead of `OUT_DIR`, for crates that
+    /// check generated code into `src/proto`.
+    pub fn out_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(dir.into());
+        self
+    }
+
+    /// Generate a `validate(&self) -> Result<(), ValidationError>` impl
+    /// for every message with protoc-gen-validate constraints, derived
+    /// from a `FileDescriptorSet` emitted to `OUT_DIR/file_descriptor_set.bin`
+    /// alongside the generated code. That descriptor set is also exposed
+    /// as `FILE_DESCRIPTOR_SET` for gRPC server reflection or dynamic
+    /// decoding (see [`crate::reflection`]).
+    pub fn with_validation(mut self) -> Self {
+        self.validate = true;
+        self
+    }
+
+    /// Resolve these external proto dependencies from a vendored copy or
+    /// a git cache before falling back to `buf export`, so the build
+    /// doesn't hard-depend on `buf` being installed.
+    pub fn with_git_fallback_deps(mut self, deps: &[GitDependency]) -> Self {
+        self.git_fallback_deps.extend_from_slice(deps);
+        self
+    }
+
+    /// Resolve buf dependencies (if requested) and invoke `tonic_build`
+    /// over the discovered proto files.
+    pub fn compile(mut self) -> BuildResult<()> {
+        if self.proto_files.is_empty() {
+            return Err(format!(
+                "no .proto files discovered under {}",
+                self.package_root.display()
+            )
+            .into());
+        }
+
+        resolve_protoc()?;
+
+        if !self.git_fallback_deps.is_empty() {
+            let out_dir = PathBuf::from(std::env::var("OUT_DIR")?);
+            let mut unresolved = Vec::new();
+            for dep in std::mem::take(&mut self.git_fallback_deps) {
+                match vendor::resolve_include_dir(&self.package_root, &out_dir, &dep) {
+                    Some(include_dir) => self.include_dirs.push(include_dir),
+                    None => unresolved.push(dep.name),
+                }
+            }
+            if !unresolved.is_empty() {
+                eprintln!(
+                    "cargo:warning=could not fetch {unresolved:?} from git; falling back to buf export"
+                );
+                self.buf_export = true;
+            }
+        }
+
+        if self.buf_export {
+            let out_dir = std::env::var("OUT_DIR")?;
+            let buf_deps_dir = PathBuf::from(&out_dir).join("buf_deps");
+
+            if Command::new("buf")
+                .args([
+                    "export",
+                    self.package_root.to_str().unwrap(),
+                    "-o",
+                    buf_deps_dir.to_str().unwrap(),
+                ])
+                .current_dir(&self.package_root)
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false)
+            {
+                self.include_dirs.push(buf_deps_dir);
+            } else {
+                eprintln!(
+                    "cargo:warning=buf export failed. Proto dependencies may not be available."
+                );
+                eprintln!("cargo:warning=Install buf from https://docs.buf.build/installation");
+            }
+        }
+
+        let descriptor_set_path = if self.validate {
+            Some(PathBuf::from(std::env::var("OUT_DIR")?).join("file_descriptor_set.bin"))
+        } else {
+            None
+        };
+
+        let mut config = tonic_build::configure();
+        if let Some(gates) = self.feature_gates {
+            // Neither feature set (no server/client features declared on
+            // this crate yet, or built with --no-default-features): fall
+            // back to client-only rather than silently generating nothing.
+            config = if gates.server || gates.client {
+                config.build_server(gates.server).build_client(gates.client)
+            } else {
+                config.build_server(false).build_client(true)
+            };
+            if gates.serde {
+                config = config
+                    .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+                    .type_attribute(".", "#[serde(rename_all = \"camelCase\")]");
+            }
+        }
+        if let Some(out_dir) = &self.out_dir {
+            config = config.out_dir(out_dir);
+        }
+        if let Some(descriptor_set_path) = &descriptor_set_path {
+            config = config.file_descriptor_set_path(descriptor_set_path);
+        }
+
+        config.compile_protos(&self.proto_files, &self.include_dirs)?;
+
+        if self.validate {
+            let proto_root = self.package_root.join("proto");
+            let local_proto_files: Vec<String> = self
+                .proto_files
+                .iter()
+                .filter_map(|path| path.strip_prefix(&proto_root).ok())
+                .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+                .collect();
+
+            let validate_out = PathBuf::from(std::env::var("OUT_DIR")?).join("validate.rs");
+            validate::generate_validators(
+                descriptor_set_path.as_ref().unwrap(),
+                &validate_out,
+                &local_proto_files,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Recursively discover all `.proto` files in a directory, sorted for
+/// consistent ordering across platforms.
+pub(crate) fn discover_proto_files(dir: &Path) -> BuildResult<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Err(format!("directory does not exist: {}", dir.display()).into());
+    }
+
+    let mut proto_files = Vec::new();
+    visit_dirs(dir, &mut proto_files)?;
+    proto_files.sort();
+
+    Ok(proto_files)
+}
+
+/// Recursively visit directories to find `.proto` files.
+fn visit_dirs(dir: &Path, proto_files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            visit_dirs(&path, proto_files)?;
+        } else if path.extension().and_then(|s| s.to_str()) == Some("proto") {
+            proto_files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Accessors for a crate's embedded `FILE_DESCRIPTOR_SET`.
+//!
+//! Every subcrate built with [`crate::Builder::with_validation`] embeds
+//! its compiled `FileDescriptorSet` as `pub const FILE_DESCRIPTOR_SET: &[u8]`.
+//! These helpers turn those bytes into a tonic-reflection service or a
+//! dynamically-decoded message, for consumers that don't want to depend
+//! on the crate's generated Rust types.
+
+use prost_reflect::{DescriptorPool, DynamicMessage};
+use tonic_reflection::server::v1::{ServerReflection, ServerReflectionServer};
+
+use crate::BuildResult;
+
+/// Build a tonic-reflection v1 service over `descriptor_set_bytes` (a
+/// crate's `FILE_DESCRIPTOR_SET`), for registering alongside the crate's
+/// gRPC services.
+pub fn server(
+    descriptor_set_bytes: &'static [u8],
+) -> BuildResult<ServerReflectionServer<impl ServerReflection>> {
+    Ok(tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(descriptor_set_bytes)
+        .build_v1()?)
+}
+
+/// Decode `bytes` as `full_message_name` (e.g. `"accounts.v1.Account"`)
+/// using `descriptor_set_bytes`, without compile-time knowledge of the
+/// message's Rust type.
+pub fn decode_dynamic(
+    descriptor_set_bytes: &[u8],
+    full_message_name: &str,
+    bytes: &[u8],
+) -> BuildResult<DynamicMessage> {
+    let pool = DescriptorPool::decode(descriptor_set_bytes)?;
+    let message = pool.get_message_by_name(full_message_name).ok_or_else(|| {
+        format!("message `{full_message_name}` not found in the descriptor set")
+    })?;
+    Ok(DynamicMessage::decode(message, bytes)?)
+}
@@ -0,0 +1,141 @@
+//! `protoc` toolchain resolution.
+//!
+//! Resolution order mirrors what most prost/tonic consumers expect:
+//! an explicit `PROTOC` override is trusted (and must be usable), then a
+//! system `protoc` on `PATH` is probed for a minimum version, and finally
+//! a binary vendored under this crate's `bin/` directory is used as a
+//! last resort so builds still work on machines without `protoc`
+//! installed.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::BuildResult;
+
+/// Minimum `protoc` version this crate's proto files are known to compile
+/// against. Bump alongside any proto3-only feature the contracts start
+/// relying on.
+const MIN_PROTOC_VERSION: (u32, u32, u32) = (3, 15, 0);
+
+/// Resolve a usable `protoc` binary and export it via the `PROTOC`
+/// environment variable so `prost_build`/`tonic_build` pick it up.
+///
+/// Order of preference:
+/// 1. `PROTOC` env var, if set — hard error if its version is unusable.
+/// 2. `protoc` on `PATH`, if its version meets [`MIN_PROTOC_VERSION`].
+/// 3. A binary vendored in `bin/` for the current OS/arch.
+pub fn resolve_protoc() -> BuildResult<PathBuf> {
+    if let Ok(protoc) = std::env::var("PROTOC") {
+        let path = PathBuf::from(protoc);
+        let version = protoc_version(&path).map_err(|e| {
+            format!(
+                "PROTOC={} is set but could not be run: {e}",
+                path.display()
+            )
+        })?;
+        if version < MIN_PROTOC_VERSION {
+            return Err(format!(
+                "PROTOC={} reports version {}.{}.{}, but at least {}.{}.{} is required",
+                path.display(),
+                version.0,
+                version.1,
+                version.2,
+                MIN_PROTOC_VERSION.0,
+                MIN_PROTOC_VERSION.1,
+                MIN_PROTOC_VERSION.2
+            )
+            .into());
+        }
+        std::env::set_var("PROTOC", &path);
+        return Ok(path);
+    }
+
+    let system_protoc = PathBuf::from("protoc");
+    if let Ok(version) = protoc_version(&system_protoc) {
+        if version >= MIN_PROTOC_VERSION {
+            std::env::set_var("PROTOC", &system_protoc);
+            return Ok(system_protoc);
+        }
+        eprintln!(
+            "cargo:warning=system protoc {}.{}.{} is older than the required {}.{}.{}, falling back to vendored protoc",
+            version.0, version.1, version.2,
+            MIN_PROTOC_VERSION.0, MIN_PROTOC_VERSION.1, MIN_PROTOC_VERSION.2
+        );
+    }
+
+    let vendored = vendored_protoc_path()?;
+    std::env::set_var("PROTOC", &vendored);
+    Ok(vendored)
+}
+
+/// Run `protoc --version` and parse its `libprotoc X.Y.Z` output.
+fn protoc_version(protoc: &Path) -> BuildResult<(u32, u32, u32)> {
+    let output = Command::new(protoc).arg("--version").output()?;
+    if !output.status.success() {
+        return Err(format!("{} --version exited unsuccessfully", protoc.display()).into());
+    }
+    let stdout = String::from_utf8(output.stdout)?;
+    parse_libprotoc_version(stdout.trim())
+        .ok_or_else(|| format!("unrecognized `protoc --version` output: {stdout}").into())
+}
+
+/// Parse `"libprotoc 3.21.12"` into `(3, 21, 12)`.
+fn parse_libprotoc_version(output: &str) -> Option<(u32, u32, u32)> {
+    let version = output.strip_prefix("libprotoc ")?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next().unwrap_or("0").parse().ok()?;
+    Some((major, minor, patch))
+}
+
+/// Locate the vendored `protoc` binary matching the host OS/arch under
+/// this crate's `bin/` directory, e.g. `bin/protoc-linux-x86_64`.
+fn vendored_protoc_path() -> BuildResult<PathBuf> {
+    let os = match std::env::consts::OS {
+        "macos" => "macos",
+        "linux" => "linux",
+        "windows" => "windows",
+        other => return Err(format!("no vendored protoc for OS `{other}`").into()),
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "x86_64",
+        "aarch64" => "aarch64",
+        other => return Err(format!("no vendored protoc for arch `{other}`").into()),
+    };
+    let ext = if os == "windows" { ".exe" } else { "" };
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("bin")
+        .join(format!("protoc-{os}-{arch}{ext}"));
+
+    if !path.exists() {
+        return Err(format!(
+            "no usable protoc found on PATH and no vendored binary at {}",
+            path.display()
+        )
+        .into());
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_standard_version_output() {
+        assert_eq!(parse_libprotoc_version("libprotoc 3.21.12"), Some((3, 21, 12)));
+    }
+
+    #[test]
+    fn parses_two_component_version_output() {
+        assert_eq!(parse_libprotoc_version("libprotoc 3.15"), Some((3, 15, 0)));
+    }
+
+    #[test]
+    fn rejects_unrecognized_output() {
+        assert_eq!(parse_libprotoc_version("not protoc"), None);
+    }
+}
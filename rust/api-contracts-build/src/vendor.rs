@@ -0,0 +1,128 @@
+//! Git-pinned fallback resolution for external proto dependencies.
+//!
+//! `buf export` is the default way to pull in external proto trees (e.g.
+//! `googleapis`, protoc-gen-validate's `validate.proto`), but it requires
+//! `buf` to be installed and reachable. This module fetches the same
+//! trees directly from git, pinned by commit, for locked-down
+//! environments that can reach git but can't install buf.
+//!
+//! Resolution order per dependency: a locally vendored copy under
+//! `<package_root>/vendor/<name>`, then a previously-fetched git cache
+//! under `OUT_DIR`, then a fresh shallow clone. If the clone itself
+//! fails, the caller falls back to `buf export` for whatever couldn't be
+//! resolved from git.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::BuildResult;
+
+/// An external proto tree pinned by git commit.
+#[derive(Debug, Clone, Copy)]
+pub struct GitDependency {
+    /// Short name used for the vendor/cache directory, e.g. `"googleapis"`.
+    pub name: &'static str,
+    pub repo_url: &'static str,
+    pub branch: &'static str,
+    pub commit: &'static str,
+    /// Subdirectory within the repo that contains the `.proto` files to
+    /// include, relative to the repo root (empty string for the root).
+    pub subdir: &'static str,
+}
+
+/// `googleapis`, for the common-type/annotation protos the contracts
+/// depend on (`google.api.*`, `google.rpc.*`, ...).
+pub const GOOGLEAPIS: GitDependency = GitDependency {
+    name: "googleapis",
+    repo_url: "https://github.com/googleapis/googleapis.git",
+    branch: "master",
+    commit: "06850ab2484884331411453ce7a5e1965e9a7fb2",
+    subdir: "",
+};
+
+/// `protoc-gen-validate`'s `validate/validate.proto`, needed to compile
+/// the `(validate.rules)` annotations consumed by [`crate::validate`].
+pub const PROTOC_GEN_VALIDATE: GitDependency = GitDependency {
+    name: "protoc-gen-validate",
+    repo_url: "https://github.com/bufbuild/protoc-gen-validate.git",
+    branch: "main",
+    commit: "8e32a98a0e3004e3c42b8c32c3474a2fbd92f0e2",
+    subdir: "",
+};
+
+/// Resolve `dep` to an include directory using the vendored-copy, then
+/// git-cache, then fresh-clone order described above. Returns `None` (and
+/// leaves a `cargo:warning`) if a fresh clone is needed but fails, so the
+/// caller can fall back to `buf export`.
+pub fn resolve_include_dir(package_root: &Path, out_dir: &Path, dep: &GitDependency) -> Option<PathBuf> {
+    let vendored = package_root.join("vendor").join(dep.name);
+    if vendored.exists() {
+        return Some(vendored);
+    }
+
+    let cache_dir = out_dir.join("git_deps").join(format!("{}-{}", dep.name, dep.commit));
+    if cache_dir.join(".git").exists() {
+        return Some(cache_dir.join(dep.subdir));
+    }
+
+    match shallow_clone(dep, &cache_dir) {
+        Ok(()) => Some(cache_dir.join(dep.subdir)),
+        Err(e) => {
+            eprintln!(
+                "cargo:warning=failed to fetch {} from {}: {e}",
+                dep.name, dep.repo_url
+            );
+            let _ = std::fs::remove_dir_all(&cache_dir);
+            None
+        }
+    }
+}
+
+/// Shallow-clone `dep` into `cache_dir` and check out the pinned commit.
+fn shallow_clone(dep: &GitDependency, cache_dir: &Path) -> BuildResult<()> {
+    std::fs::create_dir_all(cache_dir)?;
+    run_git(&["init", "--quiet"], cache_dir)?;
+    run_git(&["remote", "add", "origin", dep.repo_url], cache_dir)?;
+    run_git(
+        &["fetch", "--quiet", "--depth", "1", "origin", dep.commit],
+        cache_dir,
+    )?;
+    run_git(&["checkout", "--quiet", "FETCH_HEAD"], cache_dir)?;
+    Ok(())
+}
+
+fn run_git(args: &[&str], dir: &Path) -> BuildResult<()> {
+    let status = Command::new("git").args(args).current_dir(dir).status()?;
+    if !status.success() {
+        return Err(format!("`git {}` failed in {}", args.join(" "), dir.display()).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `shallow_clone` fetches by `dep.commit` directly; a truncated or
+    /// otherwise malformed SHA fails that fetch silently (the caller just
+    /// falls back to `buf export`), so pin mistakes go unnoticed unless
+    /// something asserts the format here.
+    #[test]
+    fn pinned_commits_are_full_length_hex_shas() {
+        for dep in [GOOGLEAPIS, PROTOC_GEN_VALIDATE] {
+            assert_eq!(
+                dep.commit.len(),
+                40,
+                "{}'s pinned commit {:?} is not a 40-char SHA",
+                dep.name,
+                dep.commit
+            );
+            assert!(
+                dep.commit.chars().all(|c| c.is_ascii_hexdigit()),
+                "{}'s pinned commit {:?} is not valid hex",
+                dep.name,
+                dep.commit
+            );
+        }
+    }
+}
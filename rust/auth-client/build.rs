@@ -1,13 +1,25 @@
+use std::path::PathBuf;
+
+use api_contracts_build::{GOOGLEAPIS, PROTOC_GEN_VALIDATE};
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tonic_build::configure()
-        .build_server(false)
-        .build_client(true)
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")?;
+    let project_root = PathBuf::from(&manifest_dir)
+        .join("../..")
+        .canonicalize()?;
+
+    let auth_proto_dir = project_root.join("proto").join("auth");
+
+    api_contracts_build::Builder::new(project_root)
+        .discover(auth_proto_dir)?
+        .with_git_fallback_deps(&[GOOGLEAPIS, PROTOC_GEN_VALIDATE])
+        .with_buf_export()
+        .with_feature_gated_codegen(
+            cfg!(feature = "server"),
+            cfg!(feature = "client"),
+            cfg!(feature = "serde"),
+        )
         .out_dir("src/proto")
-        .compile(
-            &[
-                "../../proto/auth/v1/auth.proto",
-            ],
-            &["../../proto"],
-        )?;
-    Ok(())
+        .with_validation()
+        .compile()
 }
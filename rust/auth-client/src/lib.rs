@@ -1,7 +1,18 @@
 
+pub use api_contracts_build::{Validate, ValidationError};
+
+/// Compiled `FileDescriptorSet` for `auth.v1`, for registering with
+/// `tonic-reflection` (see [`api_contracts_build::reflection`]) or
+/// decoding messages dynamically.
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/file_descriptor_set.bin"));
+
 pub mod auth {
     pub mod v1 {
+        use api_contracts_build::{Validate, ValidationError};
+
         tonic::include_proto!("auth.v1");
+        include!(concat!(env!("OUT_DIR"), "/validate.rs"));
     }
 }
 